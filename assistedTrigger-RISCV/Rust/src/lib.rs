@@ -0,0 +1,10 @@
+//! Storage and orchestration for the zk-IoT assisted-trigger verifier.
+//!
+//! [`store`] persists and restores proofs and their circuits; [`prover`]
+//! wraps circuit setup, execution, proving, and verification behind a single
+//! [`prover::ProverClient`]; [`recursion`] aggregates many device proofs into
+//! one via recursive verification.
+
+pub mod prover;
+pub mod recursion;
+pub mod store;
@@ -0,0 +1,132 @@
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::iop::generator::generate_partial_witness;
+use plonky2::iop::target::Target;
+use plonky2::iop::witness::{PartialWitness, Witness};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::{CircuitConfig, CircuitData};
+use plonky2::plonk::config::PoseidonGoldilocksConfig;
+use plonky2::plonk::proof::ProofWithPublicInputs;
+
+use crate::store::{self, ProofIoError};
+
+type F = GoldilocksField;
+type C = PoseidonGoldilocksConfig;
+const D: usize = 2;
+
+/// A circuit a device wants to prove statements about.
+///
+/// Implementors describe how to build the constraint system once, how to bind a
+/// concrete set of `Inputs` into a witness, and how to interpret the resulting
+/// output wires. [`ProverClient`] drives these hooks so integrators never touch
+/// the `CircuitBuilder`/`PartialWitness` plumbing directly.
+pub trait DeviceCircuit: Sized {
+    /// Caller-facing inputs bound into the witness before proving.
+    type Inputs;
+    /// Caller-facing outputs read back from the circuit's output wires.
+    type Outputs;
+
+    /// Builds the circuit, returning the handle used to assign inputs and read
+    /// outputs. The builder's gates fix the `CircuitData` produced by
+    /// [`ProverClient::setup`].
+    fn build(builder: &mut CircuitBuilder<F, D>) -> Self;
+
+    /// Assigns `inputs` onto the circuit's input targets.
+    fn set_inputs(&self, pw: &mut PartialWitness<F>, inputs: &Self::Inputs);
+
+    /// The wires whose values make up [`Self::Outputs`].
+    fn output_targets(&self) -> Vec<Target>;
+
+    /// Reconstructs the outputs from the resolved values of
+    /// [`Self::output_targets`], in the same order.
+    fn outputs(&self, values: &[F]) -> Self::Outputs;
+}
+
+/// Capacity-planning report for a single [`ProverClient::execute`] run.
+///
+/// Lets integrators size a device against a circuit without paying for a full
+/// proof.
+#[derive(Debug, Clone, Copy)]
+pub struct ExecutionReport {
+    /// Number of distinct gate types used in the circuit.
+    pub num_gate_types: usize,
+    /// Trace length in rows (`2^k`, the FRI-padded circuit degree).
+    pub degree: usize,
+    /// Number of wires per row.
+    pub num_wires: usize,
+    /// Number of public inputs.
+    pub num_public_inputs: usize,
+}
+
+/// Single entry point wrapping circuit setup, proving, verification, and
+/// witness-only execution.
+pub struct ProverClient<Ckt: DeviceCircuit> {
+    circuit: Ckt,
+    data: CircuitData<F, C, D>,
+}
+
+impl<Ckt: DeviceCircuit> ProverClient<Ckt> {
+    /// Builds the circuit and its `CircuitData`.
+    pub fn setup() -> Self {
+        Self::setup_with(CircuitConfig::standard_recursion_config())
+    }
+
+    /// Builds the circuit using a caller-supplied [`CircuitConfig`].
+    pub fn setup_with(config: CircuitConfig) -> Self {
+        let mut builder = CircuitBuilder::<F, D>::new(config);
+        let circuit = Ckt::build(&mut builder);
+        let data = builder.build::<C>();
+        Self { circuit, data }
+    }
+
+    /// The built circuit data, e.g. for persisting alongside a proof.
+    pub fn circuit_data(&self) -> &CircuitData<F, C, D> {
+        &self.data
+    }
+
+    /// Runs witness generation without proving and returns the circuit's
+    /// outputs together with a capacity report.
+    pub fn execute(&self, inputs: &Ckt::Inputs) -> Result<(Ckt::Outputs, ExecutionReport), ProofIoError> {
+        let mut pw = PartialWitness::new();
+        self.circuit.set_inputs(&mut pw, inputs);
+        let witness = generate_partial_witness(pw, &self.data.prover_only, &self.data.common);
+
+        let values: Vec<F> = self
+            .circuit
+            .output_targets()
+            .into_iter()
+            .map(|t| witness.get_target(t))
+            .collect();
+        let outputs = self.circuit.outputs(&values);
+
+        let common = &self.data.common;
+        let report = ExecutionReport {
+            num_gate_types: common.gates.len(),
+            degree: common.degree(),
+            num_wires: common.config.num_wires,
+            num_public_inputs: common.num_public_inputs,
+        };
+        Ok((outputs, report))
+    }
+
+    /// Binds `inputs` and produces a proof.
+    pub fn prove(&self, inputs: &Ckt::Inputs) -> Result<ProofWithPublicInputs<F, C, D>, ProofIoError> {
+        let mut pw = PartialWitness::new();
+        self.circuit.set_inputs(&mut pw, inputs);
+        self.data
+            .prove(pw)
+            .map_err(|e| ProofIoError::Prove(format!("{e:?}")))
+    }
+
+    /// Verifies a proof against this client's circuit.
+    pub fn verify(&self, proof: &ProofWithPublicInputs<F, C, D>) -> Result<(), ProofIoError> {
+        self.data
+            .verify(proof.clone())
+            .map_err(|e| ProofIoError::Verification(format!("{e:?}")))
+    }
+
+    /// Persists a proof and this client's circuit under the configurable build
+    /// directory, namespaced by `id` (see [`store::save_proof_and_circuit_for_id`]).
+    pub fn cache(&self, proof: &ProofWithPublicInputs<F, C, D>, id: &str) -> Result<(), ProofIoError> {
+        store::save_proof_and_circuit_for_id(proof, &self.data, id)
+    }
+}
@@ -0,0 +1,86 @@
+use plonky2::field::goldilocks_field::GoldilocksField;
+use plonky2::iop::witness::{PartialWitness, WitnessWrite};
+use plonky2::plonk::circuit_builder::CircuitBuilder;
+use plonky2::plonk::circuit_data::{CircuitConfig, CircuitData};
+use plonky2::plonk::config::PoseidonGoldilocksConfig;
+use plonky2::plonk::proof::ProofWithPublicInputs;
+
+use crate::store::{self, ProofIoError};
+
+type F = GoldilocksField;
+type C = PoseidonGoldilocksConfig;
+const D: usize = 2;
+
+/// An inner proof together with the circuit it was produced by.
+///
+/// A gateway collects one of these per device and feeds the batch to
+/// [`aggregate`].
+pub type InnerProof = (ProofWithPublicInputs<F, C, D>, CircuitData<F, C, D>);
+
+/// Recursively verifies a batch of device proofs inside a single circuit and
+/// emits one aggregated proof.
+///
+/// Each inner proof is verified by a recursive verifier gadget, so downstream
+/// consumers verify the returned proof once instead of every device proof
+/// individually. The inner proofs' public inputs are re-exposed, in order, as
+/// the public inputs of the aggregated proof. This is the recursive-verifier
+/// aggregation pattern plonky2-based zkEVM provers use to collapse many table
+/// proofs into one.
+///
+/// The aggregated proof carries its own `CircuitData`, returned alongside it;
+/// verifying the aggregate needs only that data, which [`save_aggregated`]
+/// persists so the compressed proof round-trips.
+///
+/// Limitation: the aggregation circuit is built with one recursive
+/// `verify_proof` per inner proof, so its shape — and therefore the returned
+/// `CircuitData` — depends on the batch's arity and inner circuit shapes.
+/// Batches with differing shapes produce aggregates with differing verifier
+/// data, which downstream must pin per shape; this is not yet wrapped to a
+/// single fixed-arity verifier that verifies every aggregate uniformly.
+pub fn aggregate(inner: &[InnerProof]) -> Result<InnerProof, ProofIoError> {
+    aggregate_with_config(inner, CircuitConfig::standard_recursion_config())
+}
+
+/// Like [`aggregate`] but with a caller-supplied recursion [`CircuitConfig`].
+pub fn aggregate_with_config(
+    inner: &[InnerProof],
+    config: CircuitConfig,
+) -> Result<InnerProof, ProofIoError> {
+    if inner.is_empty() {
+        return Err(ProofIoError::InvalidInput(
+            "cannot aggregate an empty proof batch".to_string(),
+        ));
+    }
+
+    let mut builder = CircuitBuilder::<F, D>::new(config);
+    let mut pw = PartialWitness::new();
+
+    for (proof, data) in inner {
+        let proof_target = builder.add_virtual_proof_with_pis(&data.common);
+        let verifier_target = builder.constant_verifier_data(&data.verifier_only);
+
+        pw.set_proof_with_pis_target(&proof_target, proof);
+
+        // Re-expose the inner public inputs so they survive aggregation.
+        builder.register_public_inputs(&proof_target.public_inputs);
+        builder.verify_proof::<C>(&proof_target, &verifier_target, &data.common);
+    }
+
+    let data = builder.build::<C>();
+    let proof = data
+        .prove(pw)
+        .map_err(|e| ProofIoError::Prove(format!("{e:?}")))?;
+
+    Ok((proof, data))
+}
+
+/// Persists an aggregated proof and its aggregation circuit for `id` under the
+/// configurable build directory, so the compressed proof round-trips through
+/// [`store::load_proof_and_circuit_for_id`].
+pub fn save_aggregated(
+    proof: &ProofWithPublicInputs<F, C, D>,
+    data: &CircuitData<F, C, D>,
+    id: &str,
+) -> Result<(), ProofIoError> {
+    store::save_proof_and_circuit_for_id(proof, data, id)
+}
@@ -1,56 +1,386 @@
+use std::env;
+use std::error::Error;
+use std::fmt;
 use std::fs;
-use std::fs::File;
-use std::io::Write;
+use std::io;
+use std::path::PathBuf;
 
 use plonky2::field::goldilocks_field::GoldilocksField;
 use plonky2::plonk::circuit_data::CircuitData;
 use plonky2::plonk::config::PoseidonGoldilocksConfig;
 use plonky2::plonk::proof::ProofWithPublicInputs;
-use plonky2::util::serialization::{DefaultGateSerializer, DefaultGeneratorSerializer};
+use plonky2::plonk::config::GenericConfig;
+use plonky2::util::serialization::{
+    DefaultGateSerializer, DefaultGeneratorSerializer, GateSerializer, WitnessGeneratorSerializer,
+};
 
-pub fn save_proof_and_circuit(
+/// Errors that can occur while persisting or restoring a proof and its circuit.
+///
+/// An IoT verifier ingests proof blobs from untrusted sources, so a corrupt
+/// file, a version mismatch, or a bad path must surface as a recoverable error
+/// rather than taking down the process.
+#[derive(Debug)]
+pub enum ProofIoError {
+    /// A filesystem read/write failed.
+    Io(io::Error),
+    /// plonky2 failed to (de)serialize a proof, circuit, gate, or generator.
+    Serialization(String),
+    /// The proof did not match the circuit's common data it was loaded against.
+    ProofCommonDataMismatch,
+    /// Verifying a proof against its circuit failed.
+    Verification(String),
+    /// Witness generation or proving failed.
+    Prove(String),
+    /// A caller supplied invalid input (e.g. an empty proof batch).
+    InvalidInput(String),
+}
+
+impl fmt::Display for ProofIoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofIoError::Io(err) => write!(f, "proof I/O error: {err}"),
+            ProofIoError::Serialization(msg) => write!(f, "proof serialization error: {msg}"),
+            ProofIoError::ProofCommonDataMismatch => {
+                write!(f, "proof does not match the circuit's common data")
+            }
+            ProofIoError::Verification(msg) => write!(f, "proof verification failed: {msg}"),
+            ProofIoError::Prove(msg) => write!(f, "proof generation failed: {msg}"),
+            ProofIoError::InvalidInput(msg) => write!(f, "invalid input: {msg}"),
+        }
+    }
+}
+
+impl Error for ProofIoError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ProofIoError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ProofIoError {
+    fn from(err: io::Error) -> Self {
+        ProofIoError::Io(err)
+    }
+}
+
+/// A loaded `(proof, circuit)` pair for a generic config `C`.
+pub type ProofAndCircuit<C> = (
+    ProofWithPublicInputs<GoldilocksField, C, 2>,
+    CircuitData<GoldilocksField, C, 2>,
+);
+
+/// Base directory under which per-device proofs and circuits are stored.
+///
+/// Resolved from the `BUILD_DIR` environment variable, defaulting to `./build`
+/// so many devices' artifacts can share a configurable, namespaced location.
+pub fn build_dir() -> PathBuf {
+    PathBuf::from(env::var("BUILD_DIR").unwrap_or_else(|_| "./build".to_string()))
+}
+
+/// Returns the `(proof_path, circuit_path)` for a given circuit `id` under
+/// [`build_dir`], e.g. `{BUILD_DIR}/{id}.proof` and `{BUILD_DIR}/{id}.circuit`.
+pub fn circuit_paths(id: &str) -> (PathBuf, PathBuf) {
+    let base = build_dir();
+    (base.join(format!("{id}.proof")), base.join(format!("{id}.circuit")))
+}
+
+/// Persists a proof and circuit for `id` under the configurable build
+/// directory, creating it if necessary.
+pub fn save_proof_and_circuit_for_id(
     proof: &ProofWithPublicInputs<GoldilocksField, PoseidonGoldilocksConfig, 2>,
     data: &CircuitData<GoldilocksField, PoseidonGoldilocksConfig, 2>,
+    id: &str,
+) -> Result<(), ProofIoError> {
+    let (proof_path, circuit_path) = circuit_paths(id);
+    fs::create_dir_all(build_dir())?;
+    save_proof_and_circuit(
+        proof,
+        data,
+        &proof_path.to_string_lossy(),
+        &circuit_path.to_string_lossy(),
+    )
+}
+
+/// Restores the proof and circuit previously saved for `id` under the
+/// configurable build directory.
+pub fn load_proof_and_circuit_for_id(
+    id: &str,
+) -> Result<ProofAndCircuit<PoseidonGoldilocksConfig>, ProofIoError> {
+    let (proof_path, circuit_path) = circuit_paths(id);
+    load_proof_and_circuit(&proof_path.to_string_lossy(), &circuit_path.to_string_lossy())
+}
+
+/// A `CircuitData` baked into the binary at compile time.
+///
+/// Embedded targets with no writable filesystem cannot read `circuit.bin` at
+/// runtime, and even where a FS exists the per-run I/O is wasteful when the
+/// same circuit is reloaded every invocation. Enabling the `embedded-circuit`
+/// feature bakes the serialized `CircuitData` into the binary via
+/// `include_bytes!` and deserializes it exactly once, following the
+/// "embed circuit spec" approach used by semaphore-rs.
+///
+/// The embedded artifact path is taken from the `EMBEDDED_CIRCUIT_PATH`
+/// environment variable at build time (`include_bytes!` requires a literal
+/// path known to the compiler).
+#[cfg(feature = "embedded-circuit")]
+pub mod embedded {
+    use super::*;
+    use once_cell::sync::Lazy;
+
+    const EMBEDDED_CIRCUIT_BYTES: &[u8] = include_bytes!(env!("EMBEDDED_CIRCUIT_PATH"));
+
+    static EMBEDDED_CIRCUIT: Lazy<CircuitData<GoldilocksField, PoseidonGoldilocksConfig, 2>> =
+        Lazy::new(|| {
+            let gate_serializer = DefaultGateSerializer;
+            let generator_serializer =
+                DefaultGeneratorSerializer::<PoseidonGoldilocksConfig, 2>::default();
+            CircuitData::from_bytes(
+                EMBEDDED_CIRCUIT_BYTES,
+                &gate_serializer,
+                &generator_serializer,
+            )
+            .expect("embedded circuit bytes must deserialize")
+        });
+
+    /// Returns the circuit baked into the binary, deserializing it on first use.
+    pub fn embedded_circuit() -> &'static CircuitData<GoldilocksField, PoseidonGoldilocksConfig, 2> {
+        &EMBEDDED_CIRCUIT
+    }
+}
+
+/// Persists a proof and circuit using the supplied gate/generator serializers.
+///
+/// Generic over any `C: GenericConfig<2, F = GoldilocksField>` and over the
+/// serializers, so callers can round-trip circuits built with a non-default
+/// hash config (e.g. [`monolith`]) as long as the serializers register that
+/// config's gates and generators.
+pub fn save_proof_and_circuit_with<C, GS, WS>(
+    proof: &ProofWithPublicInputs<GoldilocksField, C, 2>,
+    data: &CircuitData<GoldilocksField, C, 2>,
+    gate_serializer: &GS,
+    generator_serializer: &WS,
     proof_path: &str,
     circuit_path: &str,
-) {
-    let gate_serializer = DefaultGateSerializer;
-    let generator_serializer = DefaultGeneratorSerializer::<PoseidonGoldilocksConfig, 2>::default();
+) -> Result<(), ProofIoError>
+where
+    C: GenericConfig<2, F = GoldilocksField>,
+    GS: GateSerializer<GoldilocksField, 2>,
+    WS: WitnessGeneratorSerializer<GoldilocksField, 2>,
+{
     let proof_bytes = proof.to_bytes();
     let data_bytes = data
-        .to_bytes(&gate_serializer, &generator_serializer)
-        .unwrap();
+        .to_bytes(gate_serializer, generator_serializer)
+        .map_err(|e| ProofIoError::Serialization(format!("{e:?}")))?;
 
-    fs::write("./proof.bin", proof_bytes).unwrap();
-    fs::write("./circuit.bin", data_bytes).unwrap();
-    
-    // let mut file = File::create(proof_path).expect("Unable to create file");
-    // write!(file, "{:#?}", proof).expect("Unable to write proof debug output");
+    fs::write(proof_path, proof_bytes)?;
+    fs::write(circuit_path, data_bytes)?;
 
-    // file = File::create(circuit_path).expect("Unable to create file");
-    // write!(file, "{:#?}", data).expect("Unable to write proof debug output");
+    Ok(())
+}
+
+/// Restores a proof and circuit using the supplied serializers.
+pub fn load_proof_and_circuit_with<C, GS, WS>(
+    gate_serializer: &GS,
+    generator_serializer: &WS,
+    proof_path: &str,
+    circuit_path: &str,
+) -> Result<ProofAndCircuit<C>, ProofIoError>
+where
+    C: GenericConfig<2, F = GoldilocksField>,
+    GS: GateSerializer<GoldilocksField, 2>,
+    WS: WitnessGeneratorSerializer<GoldilocksField, 2>,
+{
+    let circuit_bytes = fs::read(circuit_path)?;
+    let circuit = CircuitData::from_bytes(&circuit_bytes, gate_serializer, generator_serializer)
+        .map_err(|e| ProofIoError::Serialization(format!("{e:?}")))?;
+
+    let proof_bytes = fs::read(proof_path)?;
+    let proof = ProofWithPublicInputs::from_bytes(proof_bytes, &circuit.common)
+        .map_err(|e| ProofIoError::Serialization(format!("{e:?}")))?;
+
+    Ok((proof, circuit))
+}
 
+pub fn save_proof_and_circuit(
+    proof: &ProofWithPublicInputs<GoldilocksField, PoseidonGoldilocksConfig, 2>,
+    data: &CircuitData<GoldilocksField, PoseidonGoldilocksConfig, 2>,
+    proof_path: &str,
+    circuit_path: &str,
+) -> Result<(), ProofIoError> {
+    let gate_serializer = DefaultGateSerializer;
+    let generator_serializer = DefaultGeneratorSerializer::<PoseidonGoldilocksConfig, 2>::default();
+    save_proof_and_circuit_with(
+        proof,
+        data,
+        &gate_serializer,
+        &generator_serializer,
+        proof_path,
+        circuit_path,
+    )
 }
 
 pub fn load_proof_and_circuit(
     proof_path: &str,
     circuit_path: &str,
-) -> (
-    ProofWithPublicInputs<GoldilocksField, PoseidonGoldilocksConfig, 2>,
-    CircuitData<GoldilocksField, PoseidonGoldilocksConfig, 2>,
-) {
+) -> Result<ProofAndCircuit<PoseidonGoldilocksConfig>, ProofIoError> {
     let gate_serializer = DefaultGateSerializer;
     let generator_serializer = DefaultGeneratorSerializer::<PoseidonGoldilocksConfig, 2>::default();
-    let circuit_bytes = fs::read(circuit_path).unwrap();
-    let circuit = CircuitData::from_bytes(
-        &circuit_bytes,
+    load_proof_and_circuit_with(
         &gate_serializer,
         &generator_serializer,
+        proof_path,
+        circuit_path,
     )
-    .unwrap();
+}
+
+/// A Monolith-based Goldilocks configuration and the serializers needed to
+/// round-trip circuits that use it.
+///
+/// Monolith is a Goldilocks-field, zk-friendly permutation hash roughly 2–3×
+/// faster than Poseidon both natively and in-circuit, built from cheap linear
+/// MDS mixing plus a non-linear "Bars" layer of small-lookup S-boxes and a
+/// per-round constant addition. It ships a Plonky2 gate for its permutation, so
+/// swapping the hash config can cut IoT proving time substantially. The gate
+/// and generator serializers below register the Monolith gate so saved circuits
+/// deserialize correctly.
+#[cfg(feature = "monolith")]
+pub mod monolith {
+    use super::*;
+
+    use plonky2::gadgets::arithmetic::EqualityGenerator;
+    use plonky2::gadgets::arithmetic_extension::QuotientGeneratorExtension;
+    use plonky2::gadgets::range_check::LowHighGenerator;
+    use plonky2::gadgets::split_base::BaseSumGenerator;
+    use plonky2::gadgets::split_join::{SplitGenerator, WireSplitGenerator};
+    use plonky2::gates::arithmetic_base::ArithmeticBaseGenerator;
+    use plonky2::gates::arithmetic_extension::ArithmeticExtensionGenerator;
+    use plonky2::gates::base_sum::BaseSplitGenerator;
+    use plonky2::gates::coset_interpolation::InterpolationGenerator;
+    use plonky2::gates::exponentiation::ExponentiationGenerator;
+    use plonky2::gates::lookup::LookupGenerator;
+    use plonky2::gates::lookup_table::LookupTableGenerator;
+    use plonky2::gates::multiplication_extension::MulExtensionGenerator;
+    use plonky2::gates::poseidon::PoseidonGenerator;
+    use plonky2::gates::poseidon_mds::PoseidonMdsGenerator;
+    use plonky2::gates::random_access::RandomAccessGenerator;
+    use plonky2::gates::reducing::ReducingGenerator;
+    use plonky2::gates::reducing_extension::ReducingGenerator as ReducingExtensionGenerator;
+    use plonky2::iop::generator::{
+        ConstantGenerator, CopyGenerator, NonzeroTestGenerator, RandomValueGenerator,
+    };
+    use plonky2::recursion::dummy_circuit::DummyProofGenerator;
+    use plonky2::{
+        get_gate_tag_impl, get_generator_tag_impl, impl_gate_serializer,
+        impl_generator_serializer, read_gate_impl, read_generator_impl,
+    };
+    use plonky2_monolith::gates::monolith::MonolithGate;
+    use plonky2_monolith::monolith_hash::MonolithGoldilocksConfig;
 
-    let proof_bytes = fs::read(proof_path).unwrap();
-    let proof = ProofWithPublicInputs::from_bytes(proof_bytes, &circuit.common).unwrap();
+    // The serializer macros expand to trait method bodies written against `F`
+    // and `D`.
+    type F = GoldilocksField;
+    const D: usize = 2;
 
-    (proof, circuit)
+    /// Gate serializer registering plonky2's default gates plus [`MonolithGate`]
+    /// so circuits built with the Monolith permutation round-trip.
+    pub struct MonolithGateSerializer;
+
+    impl GateSerializer<F, D> for MonolithGateSerializer {
+    impl_gate_serializer! {
+        MonolithGateSerializer,
+        plonky2::gates::arithmetic_base::ArithmeticGate,
+        plonky2::gates::arithmetic_extension::ArithmeticExtensionGate<2>,
+        plonky2::gates::base_sum::BaseSumGate<2>,
+        plonky2::gates::constant::ConstantGate,
+        plonky2::gates::coset_interpolation::CosetInterpolationGate<GoldilocksField, 2>,
+        plonky2::gates::exponentiation::ExponentiationGate<GoldilocksField, 2>,
+        plonky2::gates::lookup::LookupGate,
+        plonky2::gates::lookup_table::LookupTableGate,
+        plonky2::gates::multiplication_extension::MulExtensionGate<2>,
+        plonky2::gates::noop::NoopGate,
+        plonky2::gates::poseidon::PoseidonGate<GoldilocksField, 2>,
+        plonky2::gates::poseidon_mds::PoseidonMdsGate<GoldilocksField, 2>,
+        plonky2::gates::public_input::PublicInputGate,
+        plonky2::gates::random_access::RandomAccessGate<GoldilocksField, 2>,
+        plonky2::gates::reducing::ReducingGate<2>,
+        plonky2::gates::reducing_extension::ReducingExtensionGate<2>,
+        MonolithGate<GoldilocksField, 2>
+    }
+    }
+
+    /// Generator serializer registering plonky2's default generators plus the
+    /// Monolith gate's witness generator.
+    pub struct MonolithGeneratorSerializer;
+
+    impl WitnessGeneratorSerializer<F, D> for MonolithGeneratorSerializer {
+    impl_generator_serializer! {
+        MonolithGeneratorSerializer,
+        ArithmeticBaseGenerator<GoldilocksField, 2>,
+        ArithmeticExtensionGenerator<GoldilocksField, 2>,
+        BaseSplitGenerator<2>,
+        BaseSumGenerator<2>,
+        ConstantGenerator<GoldilocksField>,
+        CopyGenerator,
+        DummyProofGenerator<GoldilocksField, MonolithGoldilocksConfig, 2>,
+        EqualityGenerator,
+        ExponentiationGenerator<GoldilocksField, 2>,
+        InterpolationGenerator<GoldilocksField, 2>,
+        LookupGenerator,
+        LookupTableGenerator,
+        LowHighGenerator,
+        MulExtensionGenerator<GoldilocksField, 2>,
+        NonzeroTestGenerator,
+        PoseidonGenerator<GoldilocksField, 2>,
+        PoseidonMdsGenerator<2>,
+        QuotientGeneratorExtension<2>,
+        RandomAccessGenerator<GoldilocksField, 2>,
+        RandomValueGenerator,
+        ReducingGenerator<2>,
+        ReducingExtensionGenerator<2>,
+        SplitGenerator,
+        WireSplitGenerator,
+        plonky2_monolith::gates::monolith::MonolithGenerator<GoldilocksField, 2>
+    }
+    }
+
+    /// Persists a Monolith-configured proof and circuit.
+    pub fn save_proof_and_circuit(
+        proof: &ProofWithPublicInputs<GoldilocksField, MonolithGoldilocksConfig, 2>,
+        data: &CircuitData<GoldilocksField, MonolithGoldilocksConfig, 2>,
+        proof_path: &str,
+        circuit_path: &str,
+    ) -> Result<(), ProofIoError> {
+        save_proof_and_circuit_with(
+            proof,
+            data,
+            &MonolithGateSerializer,
+            &MonolithGeneratorSerializer,
+            proof_path,
+            circuit_path,
+        )
+    }
+
+    /// Restores a Monolith-configured proof and circuit.
+    pub fn load_proof_and_circuit(
+        proof_path: &str,
+        circuit_path: &str,
+    ) -> Result<ProofAndCircuit<MonolithGoldilocksConfig>, ProofIoError> {
+        load_proof_and_circuit_with(
+            &MonolithGateSerializer,
+            &MonolithGeneratorSerializer,
+            proof_path,
+            circuit_path,
+        )
+    }
 }
+
+// A fast-load mode that skips the gate/generator structural re-validation in
+// `CircuitData::from_bytes` was requested (mirroring Zcash's
+// `verify_point_encodings` flag), but plonky2 0.2 exposes no deserialization
+// entry point that bypasses that work: `CircuitData::from_bytes` always
+// reconstructs and validates the full gate/generator structure. There is no
+// way to honor the request against this plonky2 version without an upstream
+// unchecked reader, so the feature is intentionally not provided rather than
+// shipped as a flag that pretends to skip work while doing the same amount.